@@ -0,0 +1,106 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::ws_server::SubscriptionSink;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tracing::warn;
+
+use sui_types::event::{Event, EventEnvelope, EventFilter, EventRegistry};
+
+use crate::json_rpc::SuiRpcModule;
+
+/// A subscribed envelope, enriched with `decoded` when `event`'s Move event
+/// type is registered in the [`EventRegistry`]. Unregistered, user-defined
+/// Move events still stream through with `decoded: None`; callers fall back
+/// to the envelope's existing layout-driven `MoveStruct` path for those.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribedEvent {
+    #[serde(flatten)]
+    pub envelope: EventEnvelope,
+    pub decoded: Option<Value>,
+}
+
+/// Subscription and query methods over the validator's event stream.
+#[rpc(server, client, namespace = "sui")]
+pub trait EventApi {
+    /// Subscribe to a stream of events matching `filter`.
+    /// `EventFilter::All` reproduces the unfiltered firehose.
+    #[subscription(name = "subscribeEvents", item = SubscribedEvent)]
+    fn subscribe_events(&self, filter: EventFilter) -> SubscriptionResult;
+}
+
+pub struct EventApiImpl {
+    event_stream: Receiver<EventEnvelope>,
+    registry: Arc<EventRegistry>,
+}
+
+impl EventApiImpl {
+    pub fn new(event_stream: Receiver<EventEnvelope>) -> Self {
+        Self {
+            event_stream,
+            registry: Arc::new(EventRegistry::standard()),
+        }
+    }
+}
+
+impl EventApiServer for EventApiImpl {
+    fn subscribe_events(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: EventFilter,
+    ) -> SubscriptionResult {
+        let mut rx = self.event_stream.resubscribe();
+        let registry = self.registry.clone();
+        tokio::spawn(async move {
+            loop {
+                let envelope = match rx.recv().await {
+                    Ok(envelope) => envelope,
+                    Err(RecvError::Lagged(skipped)) => {
+                        // Subscriber fell behind the broadcast channel's
+                        // buffer; those events are gone, but the stream
+                        // itself is still alive, so keep going instead of
+                        // silently ending it.
+                        warn!("event subscription lagged, skipped {} events", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if !envelope.matches(&filter) {
+                    // Filtered out before serialization: subscribers only pay
+                    // the cost of events they actually asked for.
+                    continue;
+                }
+                let decoded = match &envelope.event {
+                    Event::MoveEvent(move_event) => match registry.decode(move_event) {
+                        Some(Ok(value)) => Some(value),
+                        Some(Err(e)) => {
+                            warn!(
+                                "failed to decode registered Move event {}: {}",
+                                move_event.type_, e
+                            );
+                            None
+                        }
+                        None => None,
+                    },
+                    _ => None,
+                };
+                if sink.send(&SubscribedEvent { envelope, decoded }).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+impl SuiRpcModule for EventApiImpl {
+    fn rpc(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::RpcModule;
+
+/// Implemented by every `*Api` trait's server-side type so it can be folded
+/// into the single `RpcModule` the gateway serves over HTTP/WS.
+pub trait SuiRpcModule: Sized + Send + Sync + 'static {
+    fn rpc(self) -> RpcModule<Self>;
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use serde_json::Value;
+use tracing::warn;
+
+use sui_types::event::{EventRegistry, MoveEvent};
+
+use crate::json_rpc::SuiRpcModule;
+
+/// Read-only queries over events.
+#[rpc(server, client, namespace = "sui")]
+pub trait ReadApi {
+    /// Decode `event` through the standard `EventRegistry`.
+    /// Returns `null` for Move events whose type isn't registered; those are
+    /// still available, dynamically typed, via `Event::extract_move_struct`.
+    #[method(name = "decodeEvent")]
+    fn decode_event(&self, event: MoveEvent) -> RpcResult<Option<Value>>;
+}
+
+pub struct ReadApiImpl {
+    registry: EventRegistry,
+}
+
+impl Default for ReadApiImpl {
+    fn default() -> Self {
+        Self {
+            registry: EventRegistry::standard(),
+        }
+    }
+}
+
+impl ReadApiServer for ReadApiImpl {
+    fn decode_event(&self, event: MoveEvent) -> RpcResult<Option<Value>> {
+        Ok(match self.registry.decode(&event) {
+            Some(Ok(value)) => Some(value),
+            Some(Err(e)) => {
+                warn!(
+                    "failed to decode registered Move event {}: {}",
+                    event.type_, e
+                );
+                None
+            }
+            None => None,
+        })
+    }
+}
+
+impl SuiRpcModule for ReadApiImpl {
+    fn rpc(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
@@ -1,29 +1,37 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::{BTreeMap, HashMap};
+
 use move_bytecode_utils::{layout::TypeLayoutBuilder, module_cache::GetModule};
+use move_core_types::account_address::AccountAddress;
 use move_core_types::value::MoveStructLayout;
 use move_core_types::{
+    identifier::Identifier,
     language_storage::{ModuleId, StructTag, TypeTag},
     value::{MoveStruct, MoveTypeLayout},
 };
 use name_variant::NamedVariant;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::{serde_as, Bytes};
+use sha2::{Digest, Sha256};
 use strum_macros::EnumDiscriminants;
 
 use crate::object::ObjectFormatOptions;
 use crate::{
     base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest},
-    committee::EpochId,
+    committee::{Committee, EpochId},
+    crypto::{AuthorityName, AuthoritySignature},
     error::SuiError,
     messages_checkpoint::CheckpointSequenceNumber,
 };
 use schemars::JsonSchema;
 
 /// A universal Sui event type encapsulating different types of events
-#[derive(Debug, Clone, PartialEq)]
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct EventEnvelope {
     /// UTC timestamp in milliseconds since epoch (1/1/1970)
     timestamp: u64,
@@ -33,6 +41,40 @@ pub struct EventEnvelope {
     pub event: Event,
     /// json value for MoveStruct (for MoveEvent only)
     pub move_struct_json_value: Option<Value>,
+    /// Epoch the envelope was produced in. `verify` checks this against the
+    /// `Committee` the caller hands it, so a light client can't be fooled
+    /// into accepting signatures from a committee that never attested to
+    /// this epoch's validator set.
+    epoch: EpochId,
+    /// Two-leaf commitment over `{timestamp, tx_digest, event, epoch}`:
+    /// `combine(header_leaf, body_leaf)`, where `header_leaf` hashes
+    /// everything `redact` retains and `body_leaf` hashes everything it
+    /// clears (see `header_leaf`/`body_leaf`). Computed once at
+    /// construction so light clients can verify the envelope without
+    /// re-executing the transaction; stays valid after `redact()` because
+    /// `body_commitment` caches the body leaf before the bytes it covers
+    /// are thrown away.
+    pub hashes: EventHash,
+    /// `body_leaf` as of the last time the redactable fields were still
+    /// present — refreshed by `redact()` right before it clears them, so
+    /// `compute_hash` can rebuild `hashes` from a redacted envelope without
+    /// ever reading the cleared bytes. Equal to the body leaf folded into
+    /// `hashes` at construction until `redact()` is called.
+    body_commitment: EventHash,
+    /// Whether `redact()` has been called. Tells `compute_hash` whether to
+    /// take the body leaf from `self.event`'s current content or fall back
+    /// to `body_commitment`.
+    redacted: bool,
+    /// Validator signatures over `hashes`, keyed by signer. Populated as
+    /// authorities attest to the envelope; empty on a freshly constructed
+    /// envelope. `verify` requires the valid ones to reach quorum stake.
+    pub signatures: BTreeMap<AuthorityName, AuthoritySignature>,
+    /// Causal depth: 1 + the max `depth` of `prev_events`. The primary key
+    /// `order_events` sorts by, giving indexers a replay order that doesn't
+    /// depend on network arrival order.
+    depth: u64,
+    /// Transaction digests of the events this one causally follows.
+    prev_events: Vec<TransactionDigest>,
 }
 
 impl EventEnvelope {
@@ -41,18 +83,383 @@ impl EventEnvelope {
         tx_digest: Option<TransactionDigest>,
         event: Event,
         move_struct_json_value: Option<Value>,
+        epoch: EpochId,
     ) -> Self {
+        let body_commitment = Self::body_leaf(&event);
+        let hashes = Self::combine_leaves(
+            Self::header_leaf(timestamp, &tx_digest, &event, epoch),
+            body_commitment,
+        );
         Self {
             timestamp,
             tx_digest,
             event,
             move_struct_json_value,
+            epoch,
+            hashes,
+            body_commitment,
+            redacted: false,
+            signatures: BTreeMap::new(),
+            depth: 0,
+            prev_events: Vec::new(),
         }
     }
 
+    pub fn epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    /// Attach causal-ordering metadata: `depth` is 1 + the max depth of the
+    /// events in `prev_events`, which this envelope causally follows.
+    pub fn with_causality(mut self, depth: u64, prev_events: Vec<TransactionDigest>) -> Self {
+        self.depth = depth;
+        self.prev_events = prev_events;
+        self
+    }
+
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+
+    pub fn prev_events(&self) -> &[TransactionDigest] {
+        &self.prev_events
+    }
+
+    /// Recompute `self.hashes` from this envelope's current content. The
+    /// header leaf (`{timestamp, tx_digest, epoch}` plus whatever `redact`
+    /// retains of `event`) is always rebuilt fresh, so tampering with any
+    /// retained field — before or after redaction — is caught. The body
+    /// leaf (the fields `redact` clears) is rebuilt fresh from `self.event`
+    /// only if this envelope hasn't been redacted; once it has, the
+    /// original bytes are gone, so `body_commitment` — captured by
+    /// `redact()` the moment before it cleared them — stands in for it
+    /// instead. Matching `self.hashes` either way means `verify` keeps
+    /// working on a redacted envelope without ever needing the redacted
+    /// bytes back.
+    pub fn compute_hash(&self) -> EventHash {
+        let header_leaf =
+            Self::header_leaf(self.timestamp, &self.tx_digest, &self.event, self.epoch);
+        let body_leaf = if self.redacted {
+            self.body_commitment
+        } else {
+            Self::body_leaf(&self.event)
+        };
+        Self::combine_leaves(header_leaf, body_leaf)
+    }
+
+    /// Hash of `{timestamp, tx_digest, epoch}` plus the *redacted* view of
+    /// `event` — i.e. everything `Event::redact`/`MoveEvent::redact` leave
+    /// behind. Stable across redaction by construction, since it never
+    /// looks at the fields redaction clears.
+    fn header_leaf(
+        timestamp: u64,
+        tx_digest: &Option<TransactionDigest>,
+        event: &Event,
+        epoch: EpochId,
+    ) -> EventHash {
+        #[derive(Serialize)]
+        struct CanonicalHeader<'a> {
+            timestamp: u64,
+            tx_digest: &'a Option<TransactionDigest>,
+            event: &'a Event,
+            epoch: EpochId,
+        }
+        let mut retained_event = event.clone();
+        retained_event.redact();
+        // BCS gives us a canonical, deterministic encoding (no floats, no
+        // map key reordering ambiguity) so the digest is reproducible
+        // across nodes.
+        let bytes = bcs::to_bytes(&CanonicalHeader {
+            timestamp,
+            tx_digest,
+            event: &retained_event,
+            epoch,
+        })
+        .expect("BCS serialization of EventEnvelope content cannot fail");
+        EventHash::of(&bytes)
+    }
+
+    /// Hash of exactly the bytes `Event::redact` clears (`MoveEvent.contents`,
+    /// `TransferObject.destination_addr`, ...), empty for variants `redact`
+    /// leaves untouched. This is the part of the commitment that `redact()`
+    /// has to cache before it erases the bytes it covers.
+    fn body_leaf(event: &Event) -> EventHash {
+        EventHash::of(&event.redactable_bytes())
+    }
+
+    /// Fold a header and body leaf into the root commitment stored in
+    /// `self.hashes`.
+    fn combine_leaves(header_leaf: EventHash, body_leaf: EventHash) -> EventHash {
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(header_leaf.as_bytes());
+        bytes.extend_from_slice(body_leaf.as_bytes());
+        EventHash::of(&bytes)
+    }
+
+    /// Verify that `committee` is the committee of `self.epoch`, that
+    /// `self.hashes` matches the recomputed commitment, and that
+    /// `self.signatures` reach quorum stake for `committee`. Lets a light
+    /// client trust the event without re-executing the transaction. Keeps
+    /// validating a redacted envelope against the same `hashes` a
+    /// validator signed, since the body leaf for the redacted fields is
+    /// cached rather than recomputed (see `compute_hash`); any other
+    /// change, including to fields `redact` leaves behind, still fails.
+    pub fn verify(&self, committee: &Committee) -> Result<(), SuiError> {
+        if committee.epoch() != self.epoch {
+            return Err(SuiError::ObjectSerializationError {
+                error: "committee epoch does not match event envelope epoch".to_string(),
+            });
+        }
+
+        let digest = self.compute_hash();
+        if digest != self.hashes {
+            return Err(SuiError::ObjectSerializationError {
+                error: "event envelope content hash does not match recomputed hash".to_string(),
+            });
+        }
+
+        let stake: u64 = self
+            .signatures
+            .iter()
+            .filter(|(name, signature)| signature.verify(digest.as_bytes(), name).is_ok())
+            .map(|(name, _)| committee.weight(name))
+            .sum();
+        if !Self::quorum_reached(stake, committee.quorum_threshold()) {
+            return Err(SuiError::ObjectSerializationError {
+                error: "event envelope signatures do not reach quorum stake".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether `stake` reaches `threshold`. Split out from `verify` so the
+    /// stake-boundary logic is unit-testable without a full `Committee` and
+    /// signature set.
+    fn quorum_reached(stake: u64, threshold: u64) -> bool {
+        stake >= threshold
+    }
+
     pub fn event_type(&self) -> &'static str {
         self.event.variant_name()
     }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn tx_digest(&self) -> Option<TransactionDigest> {
+        self.tx_digest
+    }
+
+    /// Evaluate `filter` against this envelope. An [`EventFilter::All`] filter
+    /// degrades to matching everything, i.e. the firehose behavior subscribers
+    /// get today if they don't narrow the stream.
+    pub fn matches(&self, filter: &EventFilter) -> bool {
+        match filter {
+            EventFilter::All => true,
+            EventFilter::EventType(event_type) => self.event.event_type() == *event_type,
+            EventFilter::MoveEventType(tag_filter) => match &self.event {
+                Event::MoveEvent(move_event) => tag_filter.matches(&move_event.type_),
+                _ => false,
+            },
+            EventFilter::ObjectId(object_id) => self.event.object_id() == Some(*object_id),
+            EventFilter::ModuleId(module_id) => self.event.module_id().as_ref() == Some(module_id),
+            EventFilter::Transaction(digest) => self.tx_digest == Some(*digest),
+            EventFilter::TimeRange {
+                start_time,
+                end_time,
+            } => self.timestamp >= *start_time && self.timestamp < *end_time,
+            EventFilter::And(a, b) => self.matches(a) && self.matches(b),
+            EventFilter::Or(a, b) => self.matches(a) || self.matches(b),
+            EventFilter::Not(a) => !self.matches(a),
+        }
+    }
+
+    /// Redact this envelope's event in place: clear sensitive fields but
+    /// retain a per-variant allowlist (see `Event::redact`). Caches the
+    /// body leaf of the commitment (`body_commitment`) from the
+    /// still-present content before clearing it, so `compute_hash`/
+    /// `verify` keep validating `self.hashes`/`self.signatures` afterward
+    /// without the redacted bytes. A no-op on an already-redacted envelope.
+    pub fn redact(&mut self) {
+        if !self.redacted {
+            self.body_commitment = Self::body_leaf(&self.event);
+            self.redacted = true;
+        }
+        self.event.redact();
+        self.move_struct_json_value = None;
+    }
+}
+
+/// Produce a total order over `envelopes` independent of their network
+/// arrival order: primarily by ascending `depth`, ties broken by ascending
+/// `timestamp`, remaining ties broken by the lexicographic order of the
+/// content hash. Gives indexers a stable replay order across checkpoint
+/// reorgs and out-of-order delivery.
+pub fn order_events(envelopes: &mut [EventEnvelope]) {
+    envelopes.sort_by(|a, b| {
+        a.depth
+            .cmp(&b.depth)
+            .then_with(|| a.timestamp.cmp(&b.timestamp))
+            .then_with(|| a.hashes.as_bytes().cmp(b.hashes.as_bytes()))
+    });
+}
+
+/// Topologically sort `envelopes` respecting `prev_events` as a DAG, via
+/// Kahn's algorithm. Among nodes that are simultaneously ready, and as a
+/// safety net if a cycle is detected (which shouldn't occur for honestly
+/// produced events), ties are broken with the same depth/timestamp/hash key
+/// as `order_events`.
+pub fn topological_sort(envelopes: Vec<EventEnvelope>) -> Vec<EventEnvelope> {
+    let n = envelopes.len();
+
+    // Map each transaction digest to the envelopes it identifies, so we can
+    // resolve `prev_events` references into graph edges.
+    let mut digest_to_indices: HashMap<TransactionDigest, Vec<usize>> = HashMap::new();
+    for (i, e) in envelopes.iter().enumerate() {
+        if let Some(digest) = e.tx_digest {
+            digest_to_indices.entry(digest).or_default().push(i);
+        }
+    }
+
+    let mut in_degree = vec![0usize; n];
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (i, e) in envelopes.iter().enumerate() {
+        for prev in &e.prev_events {
+            for &parent in digest_to_indices.get(prev).into_iter().flatten() {
+                if parent != i {
+                    children[parent].push(i);
+                    in_degree[i] += 1;
+                }
+            }
+        }
+    }
+
+    let ready_key = |i: usize| {
+        let e = &envelopes[i];
+        (e.depth, e.timestamp, e.hashes.as_bytes().to_vec())
+    };
+
+    let mut visited = vec![false; n];
+    let mut ready: Vec<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+    ready.sort_by_key(|&i| ready_key(i));
+
+    let mut order = Vec::with_capacity(n);
+    while order.len() < n {
+        if ready.is_empty() {
+            // Cycle fallback: shouldn't occur for honestly produced events.
+            ready = (0..n).filter(|&i| !visited[i]).collect();
+            ready.sort_by_key(|&i| ready_key(i));
+        }
+        let i = ready.remove(0);
+        visited[i] = true;
+        order.push(i);
+        for &child in &children[i] {
+            in_degree[child] = in_degree[child].saturating_sub(1);
+            if in_degree[child] == 0 && !visited[child] {
+                ready.push(child);
+                ready.sort_by_key(|&j| ready_key(j));
+            }
+        }
+    }
+
+    let mut slots: Vec<Option<EventEnvelope>> = envelopes.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .map(|i| slots[i].take().expect("each index appears exactly once"))
+        .collect()
+}
+
+/// A composable predicate tree over [`Event`]/[`EventType`] data used to
+/// narrow a `subscribe_events` stream to the events a client actually wants.
+/// Filters are evaluated on the node before serialization, so subscribers
+/// only pay the serialization cost for events that match.
+///
+/// [`EventFilter::All`] is the empty filter: it means "record everything"
+/// and reproduces the unfiltered firehose subscribers get today.
+#[derive(Eq, Debug, Clone, PartialEq, Deserialize, Serialize, Hash, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum EventFilter {
+    /// Match every event.
+    All,
+    /// Match on the `EventType` discriminant.
+    EventType(EventType),
+    /// Match a `MoveEvent` whose `type_` falls under the given `StructTag` prefix.
+    MoveEventType(StructTagFilter),
+    /// Match `Event::object_id()`.
+    ObjectId(ObjectID),
+    /// Match `Event::module_id()`.
+    ModuleId(ModuleId),
+    /// Match the transaction digest associated with the envelope, if any.
+    Transaction(TransactionDigest),
+    /// Match envelopes whose `timestamp` falls in `[start_time, end_time)`.
+    TimeRange {
+        start_time: u64,
+        end_time: u64,
+    },
+    And(Box<EventFilter>, Box<EventFilter>),
+    Or(Box<EventFilter>, Box<EventFilter>),
+    Not(Box<EventFilter>),
+}
+
+impl EventFilter {
+    pub fn and(self, other: EventFilter) -> Self {
+        EventFilter::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: EventFilter) -> Self {
+        EventFilter::Or(Box::new(self), Box::new(other))
+    }
+}
+
+/// A `package::module::Name` prefix match on a `StructTag`, with `type_params`
+/// as the wildcard: `None` matches any type parameter list, `Some` requires
+/// an exact match.
+#[derive(Eq, Debug, Clone, PartialEq, Deserialize, Serialize, Hash, JsonSchema)]
+pub struct StructTagFilter {
+    pub address: ObjectID,
+    pub module: Identifier,
+    pub name: Identifier,
+    pub type_params: Option<Vec<TypeTag>>,
+}
+
+impl StructTagFilter {
+    pub fn matches(&self, tag: &StructTag) -> bool {
+        tag.address == self.address.into()
+            && tag.module == self.module
+            && tag.name == self.name
+            && self
+                .type_params
+                .as_ref()
+                .map_or(true, |params| &tag.type_params == params)
+    }
+}
+
+/// A SHA-256 digest: either an `EventEnvelope`'s content commitment (the
+/// hash of its header and body leaves, see `EventEnvelope::hashes`) or one
+/// of those leaves, letting a light client verify an event without
+/// re-executing its transaction.
+#[serde_as]
+#[derive(Eq, Copy, Debug, Clone, PartialEq, Deserialize, Serialize, Hash, JsonSchema)]
+pub struct EventHash(#[serde_as(as = "Bytes")] [u8; 32]);
+
+impl EventHash {
+    /// SHA-256 of `bytes`.
+    fn of(bytes: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        EventHash(hasher.finalize().into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for EventHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
 }
 
 #[derive(Eq, Debug, Clone, PartialEq, Deserialize, Serialize, Hash, JsonSchema)]
@@ -68,6 +475,7 @@ pub enum TransferType {
     Eq, Debug, Clone, PartialEq, NamedVariant, Deserialize, Serialize, Hash, EnumDiscriminants,
 )]
 #[strum_discriminants(name(EventType))]
+#[strum_discriminants(derive(Deserialize, Serialize, Hash, JsonSchema))]
 pub enum Event {
     /// Move-specific event
     MoveEvent(MoveEvent),
@@ -154,6 +562,54 @@ impl Event {
             _ => Ok(None),
         }
     }
+
+    /// Redact this event in place: clear sensitive fields but retain enough
+    /// of each variant for a downstream consumer to still see that an event
+    /// of this type occurred, with `object_id()`/`module_id()` resolving
+    /// the same way they did before redaction. The allowlist is the set of
+    /// fields each arm below leaves untouched; there's exactly one place
+    /// that defines it, this match.
+    pub fn redact(&mut self) {
+        match self {
+            // Keeps only `type_`; see `MoveEvent::redact`.
+            Event::MoveEvent(event) => event.redact(),
+            Event::TransferObject {
+                destination_addr, ..
+            } => {
+                // Keeps `object_id`, `version` and `type_`; the destination is the
+                // sensitive part of a transfer.
+                *destination_addr = SuiAddress::default();
+            }
+            // These variants already carry nothing beyond their identifying
+            // field (`package_id`/`object_id`/`epoch_id`/checkpoint
+            // sequence number), so there's nothing left to redact.
+            Event::Publish { .. }
+            | Event::DeleteObject(_)
+            | Event::NewObject(_)
+            | Event::EpochChange(_)
+            | Event::Checkpoint(_) => {}
+        }
+    }
+
+    /// Bytes of exactly the fields `redact` clears for this variant — the
+    /// mirror image of `redact`'s allowlist. Empty for variants `redact`
+    /// leaves untouched. Used to build an envelope's body commitment
+    /// (see `EventEnvelope::body_leaf`) independently of the retained
+    /// fields, so the two can be hashed, and redacted, separately.
+    fn redactable_bytes(&self) -> Vec<u8> {
+        match self {
+            Event::MoveEvent(event) => event.contents.clone(),
+            Event::TransferObject {
+                destination_addr, ..
+            } => bcs::to_bytes(destination_addr)
+                .expect("BCS serialization of SuiAddress cannot fail"),
+            Event::Publish { .. }
+            | Event::DeleteObject(_)
+            | Event::NewObject(_)
+            | Event::EpochChange(_)
+            | Event::Checkpoint(_) => Vec::new(),
+        }
+    }
 }
 
 #[serde_as]
@@ -165,6 +621,28 @@ pub struct MoveEvent {
 }
 
 impl MoveEvent {
+    /// Zero `contents`, discarding the serialized Move struct while leaving
+    /// `type_` intact so the event's type is still visible after redaction.
+    pub fn redact(&mut self) {
+        self.contents.clear();
+    }
+
+    /// Decode `self.contents` into `T` if `self.type_` matches `T::type_()`.
+    /// Returns `Ok(None)`, rather than an error, on a type mismatch, so
+    /// callers can fall back to the dynamically-typed
+    /// `to_move_struct_with_resolver` path for event types `T` doesn't
+    /// cover.
+    pub fn decode_as<T: SuiEventContent>(&self) -> Result<Option<T>, SuiError> {
+        if self.type_ != T::type_() {
+            return Ok(None);
+        }
+        bcs::from_bytes(&self.contents)
+            .map(Some)
+            .map_err(|e| SuiError::ObjectSerializationError {
+                error: e.to_string(),
+            })
+    }
+
     /// Get a `MoveStructLayout` for `self`.
     /// The `resolver` value must contain the module that declares `self.type_` and the (transitive)
     /// dependencies of `self.type_` in order for this to succeed. Failure will result in an `ObjectSerializationError`
@@ -207,3 +685,319 @@ impl MoveEvent {
         self.to_move_struct(&self.get_layout(format, resolver)?)
     }
 }
+
+/// Implemented by well-known Move event payloads so they can be registered
+/// in an [`EventRegistry`] and decoded straight into a typed Rust value,
+/// instead of falling back to the dynamically-typed `MoveStruct` path.
+pub trait SuiEventContent: DeserializeOwned + Serialize {
+    /// The Move struct this content type decodes, e.g.
+    /// `0x2::coin::CoinBalanceChangeEvent`.
+    fn type_() -> StructTag;
+}
+
+type EventDecoder = Box<dyn Fn(&[u8]) -> Result<Value, SuiError> + Send + Sync>;
+
+/// Maps a Move `StructTag` to a decoder from `MoveEvent.contents` (BCS) to a
+/// JSON value, so `event_api` and `read_api` can return typed, schema-stable
+/// payloads for common framework events (coin transfers, object creation)
+/// while still falling back to the existing layout-driven `MoveStruct` path
+/// for arbitrary, unregistered user-defined Move events.
+#[derive(Default)]
+pub struct EventRegistry {
+    decoders: HashMap<StructTag, EventDecoder>,
+}
+
+impl EventRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An `EventRegistry` preloaded with the framework events `event_api`
+    /// and `read_api` decode by default.
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+        registry.register::<CoinBalanceChangeEvent>();
+        registry.register::<NewObjectEvent>();
+        registry
+    }
+
+    /// Register `T`'s decoder under `T::type_()`.
+    pub fn register<T: SuiEventContent + 'static>(&mut self) {
+        self.decoders.insert(
+            T::type_(),
+            Box::new(|bytes| {
+                let value: T =
+                    bcs::from_bytes(bytes).map_err(|e| SuiError::ObjectSerializationError {
+                        error: e.to_string(),
+                    })?;
+                serde_json::to_value(&value).map_err(|e| SuiError::ObjectSerializationError {
+                    error: e.to_string(),
+                })
+            }),
+        );
+    }
+
+    /// Decode `event.contents` into JSON if `event.type_` is registered.
+    /// `None` means the caller should fall back to
+    /// `extract_move_struct`/`to_move_struct_with_resolver`.
+    pub fn decode(&self, event: &MoveEvent) -> Option<Result<Value, SuiError>> {
+        Some(self.decoders.get(&event.type_)?(&event.contents))
+    }
+}
+
+fn sui_framework_address() -> AccountAddress {
+    AccountAddress::from_hex_literal("0x2").expect("0x2 is a valid account address literal")
+}
+
+/// `0x2::coin::CoinBalanceChangeEvent`, emitted by the framework coin module
+/// whenever a coin transfer changes an address's balance.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CoinBalanceChangeEvent {
+    pub coin_type: String,
+    pub amount: i128,
+}
+
+impl SuiEventContent for CoinBalanceChangeEvent {
+    fn type_() -> StructTag {
+        StructTag {
+            address: sui_framework_address(),
+            module: Identifier::new("coin").unwrap(),
+            name: Identifier::new("CoinBalanceChangeEvent").unwrap(),
+            type_params: vec![],
+        }
+    }
+}
+
+/// `0x2::object::NewObjectEvent`, emitted by the framework on fresh object
+/// creation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NewObjectEvent {
+    pub object_type: String,
+}
+
+impl SuiEventContent for NewObjectEvent {
+    fn type_() -> StructTag {
+        StructTag {
+            address: sui_framework_address(),
+            module: Identifier::new("object").unwrap(),
+            name: Identifier::new("NewObjectEvent").unwrap(),
+            type_params: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn envelope_at(timestamp: u64) -> EventEnvelope {
+        EventEnvelope::new(timestamp, None, Event::Checkpoint(0), None, 0)
+    }
+
+    #[test]
+    fn event_filter_and_or_not_compose() {
+        let envelope = envelope_at(100);
+        let is_checkpoint = EventFilter::EventType(EventType::Checkpoint);
+        let in_range = EventFilter::TimeRange {
+            start_time: 0,
+            end_time: 200,
+        };
+        let out_of_range = EventFilter::TimeRange {
+            start_time: 200,
+            end_time: 300,
+        };
+
+        assert!(envelope.matches(&is_checkpoint.clone().and(in_range.clone())));
+        assert!(!envelope.matches(&is_checkpoint.clone().and(out_of_range.clone())));
+        assert!(envelope.matches(&is_checkpoint.or(out_of_range.clone())));
+        assert!(envelope.matches(&EventFilter::Not(Box::new(out_of_range))));
+        assert!(!envelope.matches(&EventFilter::Not(Box::new(in_range))));
+    }
+
+    #[test]
+    fn quorum_reached_at_stake_boundary() {
+        assert!(!EventEnvelope::quorum_reached(9, 10));
+        assert!(EventEnvelope::quorum_reached(10, 10));
+        assert!(EventEnvelope::quorum_reached(11, 10));
+    }
+
+    fn transfer_envelope(destination_addr: SuiAddress) -> EventEnvelope {
+        EventEnvelope::new(
+            100,
+            None,
+            Event::TransferObject {
+                object_id: ObjectID::ZERO,
+                version: SequenceNumber::from(1),
+                destination_addr,
+                type_: TransferType::ToAddress,
+            },
+            None,
+            0,
+        )
+    }
+
+    #[test]
+    fn redact_keeps_the_hash_valid() {
+        // Redacting clears `destination_addr` but the body leaf it folded
+        // into `hashes` was cached first, so the commitment a validator
+        // signed over keeps checking out afterward.
+        let mut envelope = transfer_envelope(SuiAddress::random_for_testing_only());
+        let hash_before = envelope.hashes;
+        envelope.redact();
+        assert_eq!(hash_before, envelope.compute_hash());
+        assert_eq!(hash_before, envelope.hashes);
+    }
+
+    #[test]
+    fn tampering_with_a_redactable_field_invalidates_the_hash() {
+        // Before redaction, the body leaf is recomputed from the live
+        // `destination_addr`, so swapping it in place is caught. (After
+        // redaction the body leaf comes from `body_commitment` instead.)
+        let mut envelope = transfer_envelope(SuiAddress::random_for_testing_only());
+        let hash_before = envelope.hashes;
+        if let Event::TransferObject {
+            destination_addr, ..
+        } = &mut envelope.event
+        {
+            *destination_addr = SuiAddress::random_for_testing_only();
+        }
+        assert_ne!(hash_before, envelope.compute_hash());
+    }
+
+    #[test]
+    fn tampering_with_a_retained_field_after_redaction_invalidates_the_hash() {
+        // The header leaf is always recomputed fresh, even on a redacted
+        // envelope, so tampering with a retained field (here, `version`)
+        // post-redaction is still caught.
+        let mut envelope = transfer_envelope(SuiAddress::random_for_testing_only());
+        envelope.redact();
+        let hash_before = envelope.hashes;
+        if let Event::TransferObject { version, .. } = &mut envelope.event {
+            *version = SequenceNumber::from(2);
+        }
+        assert_ne!(hash_before, envelope.compute_hash());
+    }
+
+    fn digest(byte: u8) -> TransactionDigest {
+        TransactionDigest::new([byte; 32])
+    }
+
+    fn envelope_with(tx: u8, depth: u64, prev: &[u8]) -> EventEnvelope {
+        EventEnvelope::new(0, Some(digest(tx)), Event::Checkpoint(0), None, 0)
+            .with_causality(depth, prev.iter().copied().map(digest).collect())
+    }
+
+    #[test]
+    fn topological_sort_respects_the_dag() {
+        // a -> b -> c (b depends on a, c depends on b), fed in scrambled order.
+        let a = envelope_with(1, 1, &[]);
+        let b = envelope_with(2, 2, &[1]);
+        let c = envelope_with(3, 3, &[2]);
+
+        let sorted = topological_sort(vec![c, a, b]);
+        let digests: Vec<_> = sorted.into_iter().map(|e| e.tx_digest().unwrap()).collect();
+        assert_eq!(digests, vec![digest(1), digest(2), digest(3)]);
+    }
+
+    #[test]
+    fn topological_sort_falls_back_on_cycle() {
+        // a and b depend on each other (shouldn't occur for honest events);
+        // c has no dependency. Every envelope must still appear exactly
+        // once, with the depth/timestamp/hash key breaking ties among
+        // whatever's "ready" at each step of the fallback.
+        let a = envelope_with(1, 2, &[2]);
+        let b = envelope_with(2, 1, &[1]);
+        let c = envelope_with(3, 0, &[]);
+
+        let sorted = topological_sort(vec![a, b, c]);
+        let digests: Vec<_> = sorted.into_iter().map(|e| e.tx_digest().unwrap()).collect();
+        assert_eq!(digests.len(), 3);
+        assert!(digests.contains(&digest(1)));
+        assert!(digests.contains(&digest(2)));
+        assert!(digests.contains(&digest(3)));
+        // `c` has no incoming edge at all, so it's ready from the start and
+        // sorts before the cycle participants.
+        assert_eq!(digests[0], digest(3));
+    }
+
+    #[test]
+    fn order_events_breaks_ties_by_timestamp_then_hash() {
+        let earlier = EventEnvelope::new(5, Some(digest(1)), Event::Checkpoint(0), None, 0)
+            .with_causality(1, vec![]);
+        let later = EventEnvelope::new(10, Some(digest(2)), Event::Checkpoint(0), None, 0)
+            .with_causality(1, vec![]);
+
+        let mut envelopes = vec![later.clone(), earlier.clone()];
+        order_events(&mut envelopes);
+        assert_eq!(envelopes[0].timestamp(), earlier.timestamp());
+        assert_eq!(envelopes[1].timestamp(), later.timestamp());
+
+        // Same depth and timestamp: the content hash decides.
+        let x = EventEnvelope::new(5, Some(digest(3)), Event::Checkpoint(0), None, 0)
+            .with_causality(1, vec![]);
+        let y = EventEnvelope::new(5, Some(digest(4)), Event::Checkpoint(0), None, 0)
+            .with_causality(1, vec![]);
+        let (low, high) = if x.hashes.as_bytes() < y.hashes.as_bytes() {
+            (x, y)
+        } else {
+            (y, x)
+        };
+        let mut tied = vec![high.clone(), low.clone()];
+        order_events(&mut tied);
+        assert_eq!(tied[0].hashes, low.hashes);
+        assert_eq!(tied[1].hashes, high.hashes);
+    }
+
+    #[test]
+    fn registry_decodes_a_registered_type() {
+        let registry = EventRegistry::standard();
+        let event = MoveEvent {
+            type_: NewObjectEvent::type_(),
+            contents: bcs::to_bytes(&NewObjectEvent {
+                object_type: "0x2::coin::Coin".to_string(),
+            })
+            .unwrap(),
+        };
+
+        let decoded = registry.decode(&event).unwrap().unwrap();
+        assert_eq!(decoded["object_type"], "0x2::coin::Coin");
+    }
+
+    #[test]
+    fn registry_returns_none_for_an_unregistered_type() {
+        let registry = EventRegistry::new();
+        let event = MoveEvent {
+            type_: NewObjectEvent::type_(),
+            contents: bcs::to_bytes(&NewObjectEvent {
+                object_type: "0x2::coin::Coin".to_string(),
+            })
+            .unwrap(),
+        };
+
+        assert!(registry.decode(&event).is_none());
+    }
+
+    #[test]
+    fn registry_surfaces_a_decode_error_for_corrupt_bytes() {
+        let registry = EventRegistry::standard();
+        let event = MoveEvent {
+            type_: NewObjectEvent::type_(),
+            contents: vec![0xff, 0xff, 0xff],
+        };
+
+        assert!(registry.decode(&event).unwrap().is_err());
+    }
+
+    #[test]
+    fn decode_as_rejects_a_type_mismatch() {
+        let event = MoveEvent {
+            type_: NewObjectEvent::type_(),
+            contents: bcs::to_bytes(&NewObjectEvent {
+                object_type: "0x2::coin::Coin".to_string(),
+            })
+            .unwrap(),
+        };
+
+        assert_eq!(event.decode_as::<CoinBalanceChangeEvent>().unwrap(), None);
+    }
+}